@@ -0,0 +1,10 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Use the vendored protoc binary unless PROTOC already names one, so
+    // this builds the same way in CI and on a fresh machine.
+    if std::env::var_os("PROTOC").is_none() {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    }
+
+    tonic_build::compile_protos("proto/hasher.proto")?;
+    Ok(())
+}
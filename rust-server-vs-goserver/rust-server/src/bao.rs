@@ -0,0 +1,180 @@
+//! A minimal bao-style verified-streaming scheme on top of BLAKE3's binary
+//! Merkle tree: the payload is split into 1 KiB chunks, each chunk's
+//! chaining value is `blake3(0x00 || chunk)`, and each interior node's
+//! chaining value is `blake3(0x01 || left || right)` (the leading byte is
+//! the leaf/parent domain-separation flag). The root is the chaining value
+//! of the whole tree. An "outboard" is every node's chaining value, stored
+//! in post-order, so a verifier holding only a slice of the original bytes
+//! can recombine the sibling chain up to the root without the rest of the
+//! content.
+//!
+//! This is a simplified scheme for benchmarking purposes — it is not wire
+//! compatible with the `bao` crate's outboard format.
+
+pub const CHUNK_SIZE: usize = 1024;
+
+const LEAF_FLAG: u8 = 0x00;
+const PARENT_FLAG: u8 = 0x01;
+
+fn leaf_cv(chunk: &[u8]) -> [u8; 32] {
+    let mut buf = Vec::with_capacity(chunk.len() + 1);
+    buf.push(LEAF_FLAG);
+    buf.extend_from_slice(chunk);
+    blake3::hash(&buf).into()
+}
+
+fn parent_cv(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut buf = [0u8; 65];
+    buf[0] = PARENT_FLAG;
+    buf[1..33].copy_from_slice(left);
+    buf[33..65].copy_from_slice(right);
+    blake3::hash(&buf).into()
+}
+
+/// BLAKE3's canonical left-subtree size: the largest power of two strictly
+/// less than `n`, so the tree stays balanced the same way for any chunk
+/// count.
+fn left_len(n: usize) -> usize {
+    let mut p = 1usize;
+    while p * 2 < n {
+        p *= 2;
+    }
+    p
+}
+
+fn node_count(leaves: usize) -> usize {
+    2 * leaves - 1
+}
+
+pub fn chunk_count(total_len: usize) -> usize {
+    if total_len == 0 {
+        1
+    } else {
+        total_len.div_ceil(CHUNK_SIZE)
+    }
+}
+
+/// Builds the full tree over `payload`, returning the root chaining value
+/// and every node's chaining value in post-order (children before parent).
+pub fn encode(payload: &[u8]) -> ([u8; 32], Vec<[u8; 32]>) {
+    let n = chunk_count(payload.len());
+    let mut outboard = Vec::with_capacity(node_count(n));
+    let root = build(payload, 0, n, &mut outboard);
+    (root, outboard)
+}
+
+fn chunk_bytes(payload: &[u8], chunk_index: usize) -> &[u8] {
+    let start = chunk_index * CHUNK_SIZE;
+    let end = (start + CHUNK_SIZE).min(payload.len());
+    &payload[start..end]
+}
+
+fn build(payload: &[u8], start: usize, end: usize, out: &mut Vec<[u8; 32]>) -> [u8; 32] {
+    let n = end - start;
+    let cv = if n == 1 {
+        leaf_cv(chunk_bytes(payload, start))
+    } else {
+        let mid = start + left_len(n);
+        let left = build(payload, start, mid, out);
+        let right = build(payload, mid, end, out);
+        parent_cv(&left, &right)
+    };
+    out.push(cv);
+    cv
+}
+
+/// Recombines the chaining values for the chunk range `[range_start,
+/// range_end)`, using `content` for chunks inside the range and the
+/// corresponding precomputed entries in `outboard` for everything outside
+/// it, and returns whether the result matches `root`. Returns `false`
+/// (rather than panicking) if `content` is shorter than the range implies.
+pub fn verify(
+    root: [u8; 32],
+    total_len: usize,
+    range_start: usize,
+    range_end: usize,
+    content: &[u8],
+    outboard: &[[u8; 32]],
+) -> bool {
+    let n = chunk_count(total_len);
+    if range_start >= range_end || range_end > n || outboard.len() != node_count(n) {
+        return false;
+    }
+
+    let mut idx = 0;
+    let recombined = match walk(0, n, outboard, &mut idx, range_start, range_end, content) {
+        Some(cv) => cv,
+        None => return false,
+    };
+
+    idx == outboard.len() && recombined == root
+}
+
+fn walk(
+    start: usize,
+    end: usize,
+    outboard: &[[u8; 32]],
+    idx: &mut usize,
+    range_start: usize,
+    range_end: usize,
+    content: &[u8],
+) -> Option<[u8; 32]> {
+    let n = end - start;
+
+    if end <= range_start || start >= range_end {
+        let count = node_count(n);
+        let cv = outboard[*idx + count - 1];
+        *idx += count;
+        return Some(cv);
+    }
+
+    if n == 1 {
+        let offset = (start - range_start) * CHUNK_SIZE;
+        let chunk_len = CHUNK_SIZE.min(content.len().saturating_sub(offset));
+        let chunk = content.get(offset..offset + chunk_len)?;
+        let cv = leaf_cv(chunk);
+        *idx += 1;
+        return Some(cv);
+    }
+
+    let mid = start + left_len(n);
+    let left = walk(start, mid, outboard, idx, range_start, range_end, content)?;
+    let right = walk(mid, end, outboard, idx, range_start, range_end, content)?;
+    *idx += 1;
+    Some(parent_cv(&left, &right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_then_verify_full_range_round_trips() {
+        let payload = b"the quick brown fox jumps over the lazy dog".repeat(64);
+        let (root, outboard) = encode(&payload);
+        let n = chunk_count(payload.len());
+
+        assert!(verify(root, payload.len(), 0, n, &payload, &outboard));
+    }
+
+    #[test]
+    fn verify_rejects_wrong_root() {
+        let payload = b"hello world".repeat(200);
+        let (_, outboard) = encode(&payload);
+        let n = chunk_count(payload.len());
+
+        assert!(!verify([0u8; 32], payload.len(), 0, n, &payload, &outboard));
+    }
+
+    #[test]
+    fn verify_returns_false_instead_of_panicking_on_short_content() {
+        let payload = vec![7u8; CHUNK_SIZE * 3];
+        let (root, outboard) = encode(&payload);
+        let n = chunk_count(payload.len());
+
+        // `content` claims to cover all 3 chunks but is far shorter than
+        // the range implies — this used to panic on an out-of-range slice.
+        let short_content = &payload[..10];
+        assert!(!verify(root, payload.len(), 0, n, short_content, &outboard));
+    }
+}
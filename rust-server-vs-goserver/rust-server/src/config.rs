@@ -0,0 +1,47 @@
+use std::env;
+
+/// HTTP/2 tuning knobs, read from env so a benchmark run can be reproduced
+/// without recompiling.
+#[derive(Clone, Copy, Debug)]
+pub struct Http2Config {
+    pub initial_stream_window_size: u32,
+    pub initial_connection_window_size: u32,
+    pub max_concurrent_streams: u32,
+}
+
+impl Default for Http2Config {
+    fn default() -> Self {
+        Self {
+            initial_stream_window_size: 1024 * 1024,
+            initial_connection_window_size: 1024 * 1024,
+            max_concurrent_streams: 200,
+        }
+    }
+}
+
+impl Http2Config {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            initial_stream_window_size: env_u32(
+                "RUST_SERVER_H2_STREAM_WINDOW",
+                default.initial_stream_window_size,
+            ),
+            initial_connection_window_size: env_u32(
+                "RUST_SERVER_H2_CONN_WINDOW",
+                default.initial_connection_window_size,
+            ),
+            max_concurrent_streams: env_u32(
+                "RUST_SERVER_H2_MAX_STREAMS",
+                default.max_concurrent_streams,
+            ),
+        }
+    }
+}
+
+fn env_u32(key: &str, default: u32) -> u32 {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
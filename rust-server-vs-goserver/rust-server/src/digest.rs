@@ -0,0 +1,95 @@
+use sha2::{Digest as _, Sha256, Sha512};
+use std::fmt;
+use std::str::FromStr;
+
+/// Upper bound on `iters` so a malicious/careless query can't pin a worker
+/// thread forever.
+pub const MAX_ITERS: u32 = 1_000_000;
+const DEFAULT_ITERS: u32 = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+impl Algorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha512 => "sha512",
+            Algorithm::Blake3 => "blake3",
+        }
+    }
+
+    /// Reads `RUST_SERVER_HASH_ALGO`, defaulting to SHA-256.
+    pub fn default_from_env() -> Self {
+        std::env::var("RUST_SERVER_HASH_ALGO")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(Algorithm::Sha256)
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sha256" | "sha-256" => Ok(Algorithm::Sha256),
+            "sha512" | "sha-512" => Ok(Algorithm::Sha512),
+            "blake3" => Ok(Algorithm::Blake3),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Reads `RUST_SERVER_HASH_ITERS`, defaulting to 100, clamped to `MAX_ITERS`.
+pub fn default_iters_from_env() -> u32 {
+    std::env::var("RUST_SERVER_HASH_ITERS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .map(|iters: u32| iters.min(MAX_ITERS))
+        .unwrap_or(DEFAULT_ITERS)
+}
+
+/// Hashes `input` with `algo`, then repeatedly re-hashes the digest
+/// `iters - 1` more times, returning the final digest hex-encoded.
+pub fn digest_hex(algo: Algorithm, input: &[u8], iters: u32) -> String {
+    let iters = iters.clamp(1, MAX_ITERS);
+
+    match algo {
+        Algorithm::Sha256 => {
+            let mut hasher = Sha256::new();
+            let mut data: [u8; 32] = Sha256::digest(input).into();
+            for _ in 1..iters {
+                hasher.update(data);
+                data = hasher.finalize_reset().into();
+            }
+            hex::encode(data)
+        }
+        Algorithm::Sha512 => {
+            let mut hasher = Sha512::new();
+            let mut data: [u8; 64] = Sha512::digest(input).into();
+            for _ in 1..iters {
+                hasher.update(data);
+                data = hasher.finalize_reset().into();
+            }
+            hex::encode(data)
+        }
+        Algorithm::Blake3 => {
+            let mut data: [u8; 32] = blake3::hash(input).into();
+            for _ in 1..iters {
+                data = *blake3::hash(&data).as_bytes();
+            }
+            hex::encode(data)
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+use tonic::{transport::Server, Request, Response, Status};
+use uuid::Uuid;
+
+pub mod pb {
+    tonic::include_proto!("hasher");
+}
+
+use pb::hasher_server::{Hasher, HasherServer};
+use pb::{FinishRequest, FinishResponse, NewHasherRequest, NewHasherResponse, UpdateRequest, UpdateResponse};
+
+/// Per-session incremental digest state. Unlike `digest::digest_hex`
+/// (which repeatedly re-hashes a fixed-size digest to simulate CPU work),
+/// this streams arbitrary client-supplied bytes the way a real
+/// content-addressing upload would.
+enum IncrementalHasher {
+    Sha256(sha2::Sha256),
+    Sha512(sha2::Sha512),
+    // Boxed: `blake3::Hasher` is ~1.9KB, nearly 10x the next-largest variant.
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl IncrementalHasher {
+    fn new(algo: &str) -> Self {
+        match algo {
+            "sha512" | "sha-512" => IncrementalHasher::Sha512(sha2::Sha512::default()),
+            "blake3" => IncrementalHasher::Blake3(Box::new(blake3::Hasher::new())),
+            _ => IncrementalHasher::Sha256(sha2::Sha256::default()),
+        }
+    }
+
+    fn update(&mut self, chunk: &[u8]) {
+        use sha2::Digest;
+        match self {
+            IncrementalHasher::Sha256(h) => h.update(chunk),
+            IncrementalHasher::Sha512(h) => h.update(chunk),
+            IncrementalHasher::Blake3(h) => {
+                h.update(chunk);
+            }
+        }
+    }
+
+    fn finish(self) -> String {
+        use sha2::Digest;
+        match self {
+            IncrementalHasher::Sha256(h) => hex::encode(h.finalize()),
+            IncrementalHasher::Sha512(h) => hex::encode(h.finalize()),
+            IncrementalHasher::Blake3(h) => hex::encode(h.finalize().as_bytes()),
+        }
+    }
+}
+
+type Sessions = Arc<RwLock<HashMap<Uuid, Arc<Mutex<IncrementalHasher>>>>>;
+
+#[derive(Default, Clone)]
+pub struct HasherService {
+    sessions: Sessions,
+}
+
+#[tonic::async_trait]
+impl Hasher for HasherService {
+    async fn new_hasher(
+        &self,
+        request: Request<NewHasherRequest>,
+    ) -> Result<Response<NewHasherResponse>, Status> {
+        let algo = request.into_inner().algo;
+        let id = Uuid::new_v4();
+
+        self.sessions
+            .write()
+            .await
+            .insert(id, Arc::new(Mutex::new(IncrementalHasher::new(&algo))));
+
+        Ok(Response::new(NewHasherResponse {
+            session_id: id.to_string(),
+        }))
+    }
+
+    async fn update(
+        &self,
+        request: Request<UpdateRequest>,
+    ) -> Result<Response<UpdateResponse>, Status> {
+        let req = request.into_inner();
+        let id = parse_session_id(&req.session_id)?;
+
+        let hasher = self
+            .sessions
+            .read()
+            .await
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| Status::not_found("unknown hasher session"))?;
+
+        hasher.lock().await.update(&req.chunk);
+
+        Ok(Response::new(UpdateResponse { ok: true }))
+    }
+
+    async fn finish(
+        &self,
+        request: Request<FinishRequest>,
+    ) -> Result<Response<FinishResponse>, Status> {
+        let req = request.into_inner();
+        let id = parse_session_id(&req.session_id)?;
+
+        let hasher = self
+            .sessions
+            .write()
+            .await
+            .remove(&id)
+            .ok_or_else(|| Status::not_found("unknown hasher session"))?;
+
+        let hasher = Arc::try_unwrap(hasher)
+            .map_err(|_| Status::failed_precondition("hasher session still in use"))?
+            .into_inner();
+
+        Ok(Response::new(FinishResponse {
+            hash: hasher.finish(),
+        }))
+    }
+}
+
+// `tonic::Status` is inherently large; boxing it here would just push the
+// cost onto every call site, which all return `Result<_, Status>` anyway.
+#[allow(clippy::result_large_err)]
+fn parse_session_id(raw: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(raw).map_err(|_| Status::invalid_argument("invalid session_id"))
+}
+
+/// Runs the gRPC server on `addr`, sharing the caller's Tokio runtime.
+pub async fn serve(addr: SocketAddr) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    println!("Rust gRPC hasher service starting on {}", addr);
+
+    Server::builder()
+        .add_service(HasherServer::new(HasherService::default()))
+        .serve(addr)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    #[tokio::test]
+    async fn new_update_finish_round_trips_to_the_plain_digest() {
+        let service = HasherService::default();
+
+        let session_id = service
+            .new_hasher(Request::new(NewHasherRequest {
+                algo: "sha256".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .session_id;
+
+        for chunk in [b"hello ".to_vec(), b"world".to_vec()] {
+            service
+                .update(Request::new(UpdateRequest {
+                    session_id: session_id.clone(),
+                    chunk,
+                }))
+                .await
+                .unwrap();
+        }
+
+        let hash = service
+            .finish(Request::new(FinishRequest {
+                session_id: session_id.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .hash;
+
+        assert_eq!(hash, hex::encode(Sha256::digest(b"hello world")));
+    }
+
+    #[tokio::test]
+    async fn update_on_unknown_session_is_not_found() {
+        let service = HasherService::default();
+
+        let status = service
+            .update(Request::new(UpdateRequest {
+                session_id: Uuid::new_v4().to_string(),
+                chunk: b"chunk".to_vec(),
+            }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn finish_on_unknown_session_is_not_found() {
+        let service = HasherService::default();
+
+        let status = service
+            .finish(Request::new(FinishRequest {
+                session_id: Uuid::new_v4().to_string(),
+            }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+}
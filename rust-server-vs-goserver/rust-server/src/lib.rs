@@ -0,0 +1,417 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use hyper_util::server::graceful::GracefulShutdown;
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::TcpListener;
+
+mod bao;
+mod config;
+mod digest;
+mod grpc;
+mod tls;
+mod upstream;
+
+use config::Http2Config;
+use digest::Algorithm;
+use upstream::PeerClient;
+
+#[derive(Serialize)]
+struct HashResponse {
+    hash: String,
+    algo: &'static str,
+    iters: u32,
+    timestamp: u128,
+    source: &'static str,
+}
+
+#[derive(Serialize)]
+struct EncodeResponse {
+    root: String,
+    outboard: String,
+    total_len: u64,
+}
+
+/// `range_start`/`range_end` are a **byte** range into the original
+/// payload (matching `EncodeResponse::total_len`), not chunk indices —
+/// `verify_handler` converts them to the chunk range `bao::verify` expects.
+#[derive(Deserialize)]
+struct VerifyRequest {
+    root: String,
+    total_len: u64,
+    range_start: u64,
+    range_end: u64,
+    content: String,
+    outboard: String,
+}
+
+#[derive(Serialize)]
+struct VerifyResponse {
+    verified: bool,
+}
+
+#[derive(Serialize)]
+struct PeerResult {
+    addr: String,
+    response: Option<serde_json::Value>,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct AggregatedHashResponse {
+    #[serde(flatten)]
+    local: HashResponse,
+    peers: Vec<PeerResult>,
+}
+
+/// Runs the HTTP(S) benchmark server on `http_addr` and the gRPC hasher
+/// service on `grpc_addr` until a shutdown signal is received, then drains
+/// in-flight HTTP requests for at most 30 seconds before returning. Split
+/// out from `main` so the
+/// integration tests can bind both to ephemeral ports instead of the
+/// well-known 8080/8081.
+pub async fn run(
+    http_addr: SocketAddr,
+    grpc_addr: SocketAddr,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let listener = TcpListener::bind(http_addr).await?;
+    let h2_config = Http2Config::from_env();
+    let graceful = GracefulShutdown::new();
+    let peers = Arc::new(PeerClient::peers_from_env());
+
+    tokio::task::spawn(async move {
+        if let Err(err) = grpc::serve(grpc_addr).await {
+            eprintln!("gRPC server error: {:?}", err);
+        }
+    });
+
+    let tls_acceptor = if tls::tls_enabled() {
+        println!("Rust server starting on {} (TLS, h1/h2 auto-negotiated)", http_addr);
+        Some(tls::build_acceptor()?)
+    } else {
+        println!("Rust server starting on {} (h1/h2 auto-negotiated)", http_addr);
+        None
+    };
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                let builder = make_builder(h2_config);
+                let watcher = graceful.watcher();
+                let peers = peers.clone();
+
+                if let Some(acceptor) = tls_acceptor.clone() {
+                    tokio::task::spawn(async move {
+                        let tls_stream = match acceptor.accept(stream).await {
+                            Ok(tls_stream) => tls_stream,
+                            Err(err) => {
+                                eprintln!("TLS handshake failed: {:?}", err);
+                                return;
+                            }
+                        };
+                        serve(builder, watcher, peers, TokioIo::new(tls_stream)).await;
+                    });
+                } else {
+                    tokio::task::spawn(async move {
+                        serve(builder, watcher, peers, TokioIo::new(stream)).await;
+                    });
+                }
+            }
+            _ = shutdown_signal() => {
+                println!("Shutdown signal received, draining in-flight requests...");
+                break;
+            }
+        }
+    }
+
+    tokio::select! {
+        _ = graceful.shutdown() => {}
+        _ = tokio::time::sleep(Duration::from_secs(30)) => {
+            eprintln!("Graceful shutdown timed out, forcing exit");
+        }
+    }
+
+    Ok(())
+}
+
+fn make_builder(h2_config: Http2Config) -> auto::Builder<TokioExecutor> {
+    let mut builder = auto::Builder::new(TokioExecutor::new());
+    builder
+        .http2()
+        .initial_stream_window_size(h2_config.initial_stream_window_size)
+        .initial_connection_window_size(h2_config.initial_connection_window_size)
+        .max_concurrent_streams(h2_config.max_concurrent_streams);
+    builder
+}
+
+async fn serve<I>(
+    builder: auto::Builder<TokioExecutor>,
+    watcher: hyper_util::server::graceful::Watcher,
+    peers: Arc<Vec<Arc<PeerClient>>>,
+    io: TokioIo<I>,
+) where
+    I: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+{
+    let service = service_fn(move |req| handle_request(req, peers.clone()));
+    let conn = builder.serve_connection(io, service);
+    if let Err(err) = watcher.watch(conn).await {
+        eprintln!("Error serving connection: {:?}", err);
+    }
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+async fn handle_request(
+    req: Request<hyper::body::Incoming>,
+    peers: Arc<Vec<Arc<PeerClient>>>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    match (req.method(), req.uri().path()) {
+        (&Method::GET, "/hash") => {
+            let query = req.uri().query().map(str::to_string);
+            Ok(hash_handler(query.as_deref(), &peers).await)
+        }
+        (&Method::GET, "/health") => Ok(health_handler()),
+        (&Method::POST, "/encode") => Ok(encode_handler(req).await),
+        (&Method::POST, "/verify") => Ok(verify_handler(req).await),
+        _ => Ok(not_found()),
+    }
+}
+
+async fn encode_handler(req: Request<hyper::body::Incoming>) -> Response<Full<Bytes>> {
+    let payload = match req.collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(_) => return bad_request("failed to read request body"),
+    };
+
+    let (root, outboard) = bao::encode(&payload);
+    let outboard_bytes: Vec<u8> = outboard.into_iter().flatten().collect();
+
+    let response = EncodeResponse {
+        root: hex::encode(root),
+        outboard: BASE64.encode(outboard_bytes),
+        total_len: payload.len() as u64,
+    };
+
+    json_response(&response)
+}
+
+async fn verify_handler(req: Request<hyper::body::Incoming>) -> Response<Full<Bytes>> {
+    let body = match req.collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(_) => return bad_request("failed to read request body"),
+    };
+
+    let req: VerifyRequest = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(_) => return bad_request("invalid JSON body"),
+    };
+
+    let verified = (|| -> Option<bool> {
+        let root: [u8; 32] = hex::decode(&req.root).ok()?.try_into().ok()?;
+        let content = BASE64.decode(&req.content).ok()?;
+        let outboard_bytes = BASE64.decode(&req.outboard).ok()?;
+        let outboard: Vec<[u8; 32]> = outboard_bytes
+            .chunks_exact(32)
+            .map(|c| c.try_into().unwrap())
+            .collect();
+
+        let (chunk_start, chunk_end) = byte_range_to_chunks(req.range_start, req.range_end);
+
+        Some(bao::verify(
+            root,
+            req.total_len as usize,
+            chunk_start,
+            chunk_end,
+            &content,
+            &outboard,
+        ))
+    })()
+    .unwrap_or(false);
+
+    json_response(&VerifyResponse { verified })
+}
+
+/// Converts a `[range_start, range_end)` *byte* range into the `bao`-chunk
+/// range covering it, rounding outward so every byte in range falls inside
+/// a covered chunk.
+fn byte_range_to_chunks(range_start: u64, range_end: u64) -> (usize, usize) {
+    let chunk_size = bao::CHUNK_SIZE as u64;
+    let chunk_start = range_start / chunk_size;
+    let chunk_end = range_end.div_ceil(chunk_size);
+    (chunk_start as usize, chunk_end as usize)
+}
+
+fn json_response<T: Serialize>(value: &T) -> Response<Full<Bytes>> {
+    let json = serde_json::to_string(value).unwrap();
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "application/json")
+        .body(Full::new(Bytes::from(json)))
+        .unwrap()
+}
+
+fn bad_request(message: &str) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(StatusCode::BAD_REQUEST)
+        .body(Full::new(Bytes::from(message.to_string())))
+        .unwrap()
+}
+
+/// Parses `algo`, `iters`, and `aggregate` out of a `/hash` query string,
+/// falling back to the startup env defaults for anything missing or
+/// invalid.
+fn parse_hash_query(query: Option<&str>) -> (Algorithm, u32, bool) {
+    let mut algo = Algorithm::default_from_env();
+    let mut iters = digest::default_iters_from_env();
+    let mut aggregate = false;
+
+    for pair in query.unwrap_or_default().split('&') {
+        let mut parts = pair.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some("algo"), Some(value)) => {
+                if let Ok(parsed) = value.parse() {
+                    algo = parsed;
+                }
+            }
+            (Some("iters"), Some(value)) => {
+                if let Ok(parsed) = value.parse::<u32>() {
+                    iters = parsed.clamp(1, digest::MAX_ITERS);
+                }
+            }
+            (Some("aggregate"), Some(value)) => {
+                aggregate = value == "1" || value.eq_ignore_ascii_case("true");
+            }
+            _ => {}
+        }
+    }
+
+    (algo, iters, aggregate)
+}
+
+fn local_hash_response(algo: Algorithm, iters: u32) -> HashResponse {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+
+    let input = format!("input-{}", timestamp);
+    let hash = digest::digest_hex(algo, input.as_bytes(), iters);
+
+    HashResponse {
+        hash,
+        algo: algo.as_str(),
+        iters,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis(),
+        source: "rust",
+    }
+}
+
+async fn hash_handler(query: Option<&str>, peers: &Arc<Vec<Arc<PeerClient>>>) -> Response<Full<Bytes>> {
+    let (algo, iters, aggregate) = parse_hash_query(query);
+    let local = local_hash_response(algo, iters);
+
+    if !aggregate || peers.is_empty() {
+        return json_response(&local);
+    }
+
+    let peer_path = format!("/hash?algo={}&iters={}", algo.as_str(), iters);
+    let mut tasks = tokio::task::JoinSet::new();
+    // Each spawned task needs its own owned `Arc<PeerClient>` to outlive this
+    // loop iteration; the clone is of the Arc, not the client itself.
+    #[allow(clippy::unnecessary_to_owned)]
+    for peer in peers.iter().cloned() {
+        let peer_path = peer_path.clone();
+        tasks.spawn(async move {
+            match peer.get(&peer_path).await {
+                Ok(body) => PeerResult {
+                    addr: peer.addr().to_string(),
+                    response: serde_json::from_slice(&body).ok(),
+                    error: None,
+                },
+                Err(err) => PeerResult {
+                    addr: peer.addr().to_string(),
+                    response: None,
+                    error: Some(format!("{:?}", err)),
+                },
+            }
+        });
+    }
+
+    let mut results = Vec::with_capacity(peers.len());
+    while let Some(result) = tasks.join_next().await {
+        if let Ok(peer_result) = result {
+            results.push(peer_result);
+        }
+    }
+
+    json_response(&AggregatedHashResponse {
+        local,
+        peers: results,
+    })
+}
+
+fn health_handler() -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(StatusCode::OK)
+        .body(Full::new(Bytes::from("OK")))
+        .unwrap()
+}
+
+fn not_found() -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(StatusCode::NOT_FOUND)
+        .body(Full::new(Bytes::from("Not Found")))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_range_to_chunks_rounds_outward_to_chunk_boundaries() {
+        let chunk_size = bao::CHUNK_SIZE as u64;
+
+        assert_eq!(byte_range_to_chunks(0, chunk_size), (0, 1));
+        // A byte range that only partially covers its last chunk still
+        // needs that whole chunk included.
+        assert_eq!(byte_range_to_chunks(0, chunk_size + 1), (0, 2));
+        // A range starting mid-chunk still needs that chunk from its start.
+        assert_eq!(byte_range_to_chunks(chunk_size + 10, chunk_size * 3), (1, 3));
+    }
+}
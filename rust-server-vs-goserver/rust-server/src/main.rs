@@ -1,98 +1,16 @@
-use http_body_util::Full;
-use hyper::body::Bytes;
-use hyper::server::conn::http1;
-use hyper::service::service_fn;
-use hyper::{Request, Response, StatusCode};
-use hyper_util::rt::TokioIo;
-use serde::Serialize;
-use sha2::{Digest, Sha256};
-use std::convert::Infallible;
 use std::net::SocketAddr;
-use std::time::{SystemTime, UNIX_EPOCH};
-use tokio::net::TcpListener;
 
-#[derive(Serialize)]
-struct HashResponse {
-    hash: String,
-    timestamp: u128,
-    source: &'static str,
+fn port_from_env(key: &str, default: u16) -> u16 {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
-    let listener = TcpListener::bind(addr).await?;
-    println!("Rust server starting on :8080");
+    let http_addr = SocketAddr::from(([0, 0, 0, 0], port_from_env("RUST_SERVER_PORT", 8080)));
+    let grpc_addr = SocketAddr::from(([0, 0, 0, 0], port_from_env("RUST_SERVER_GRPC_PORT", 8081)));
 
-    loop {
-        let (stream, _) = listener.accept().await?;
-        let io = TokioIo::new(stream);
-
-        tokio::task::spawn(async move {
-            if let Err(err) = http1::Builder::new()
-                .serve_connection(io, service_fn(handle_request))
-                .await
-            {
-                eprintln!("Error serving connection: {:?}", err);
-            }
-        });
-    }
-}
-
-async fn handle_request(
-    req: Request<hyper::body::Incoming>,
-) -> Result<Response<Full<Bytes>>, Infallible> {
-    match req.uri().path() {
-        "/hash" => Ok(hash_handler()),
-        "/health" => Ok(health_handler()),
-        _ => Ok(not_found()),
-    }
-}
-
-fn hash_handler() -> Response<Full<Bytes>> {
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_nanos();
-
-    let input = format!("input-{}", timestamp);
-
-    // SHA256 hash 100 iterations
-    let mut hasher = Sha256::new();
-    let mut data: [u8; 32] = Sha256::digest(input.as_bytes()).into();
-    for _ in 1..100 {
-        hasher.update(data);
-        data = hasher.finalize_reset().into();
-    }
-
-    let response = HashResponse {
-        hash: hex::encode(data),
-        timestamp: SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis(),
-        source: "rust",
-    };
-
-    let json = serde_json::to_string(&response).unwrap();
-
-    Response::builder()
-        .status(StatusCode::OK)
-        .header("Content-Type", "application/json")
-        .body(Full::new(Bytes::from(json)))
-        .unwrap()
-}
-
-fn health_handler() -> Response<Full<Bytes>> {
-    Response::builder()
-        .status(StatusCode::OK)
-        .body(Full::new(Bytes::from("OK")))
-        .unwrap()
-}
-
-fn not_found() -> Response<Full<Bytes>> {
-    Response::builder()
-        .status(StatusCode::NOT_FOUND)
-        .body(Full::new(Bytes::from("Not Found")))
-        .unwrap()
+    rust_server::run(http_addr, grpc_addr).await
 }
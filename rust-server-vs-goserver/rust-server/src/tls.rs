@@ -0,0 +1,65 @@
+use rustls_pemfile::{certs, private_key};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::TlsAcceptor;
+
+/// Env vars that select TLS mode and locate the PEM cert/key pair.
+pub const TLS_ENABLED_ENV: &str = "RUST_SERVER_TLS";
+pub const TLS_CERT_ENV: &str = "RUST_SERVER_TLS_CERT";
+pub const TLS_KEY_ENV: &str = "RUST_SERVER_TLS_KEY";
+
+/// Returns `true` if `RUST_SERVER_TLS` is set to a truthy value.
+pub fn tls_enabled() -> bool {
+    matches!(
+        std::env::var(TLS_ENABLED_ENV).as_deref(),
+        Ok("1") | Ok("true") | Ok("TRUE")
+    )
+}
+
+/// Loads a `rustls::ServerConfig` from the cert/key paths named by
+/// `RUST_SERVER_TLS_CERT` / `RUST_SERVER_TLS_KEY`, falling back to
+/// `cert.pem` / `key.pem` in the current directory.
+pub fn load_server_config() -> Result<Arc<ServerConfig>, Box<dyn std::error::Error + Send + Sync>> {
+    let cert_path = std::env::var(TLS_CERT_ENV).unwrap_or_else(|_| "cert.pem".to_string());
+    let key_path = std::env::var(TLS_KEY_ENV).unwrap_or_else(|_| "key.pem".to_string());
+
+    let chain = load_certs(&cert_path)?;
+    let key = load_key(&key_path)?;
+
+    let config = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(chain, key)?;
+
+    Ok(Arc::new(config))
+}
+
+pub fn build_acceptor() -> Result<TlsAcceptor, Box<dyn std::error::Error + Send + Sync>> {
+    // reqwest (pulled in only by the integration tests) and tokio-rustls both
+    // depend on rustls but can disagree on which crypto backend is the
+    // process default; install one explicitly so it's not left ambiguous.
+    // Ignore the error: it just means another caller already installed one.
+    let _ = tokio_rustls::rustls::crypto::aws_lc_rs::default_provider().install_default();
+
+    Ok(TlsAcceptor::from(load_server_config()?))
+}
+
+fn load_certs(
+    path: impl AsRef<Path>,
+) -> Result<Vec<tokio_rustls::rustls::pki_types::CertificateDer<'static>>, Box<dyn std::error::Error + Send + Sync>>
+{
+    let file = File::open(path)?;
+    let mut reader = BufReader::new(file);
+    Ok(certs(&mut reader).collect::<Result<Vec<_>, _>>()?)
+}
+
+fn load_key(
+    path: impl AsRef<Path>,
+) -> Result<tokio_rustls::rustls::pki_types::PrivateKeyDer<'static>, Box<dyn std::error::Error + Send + Sync>>
+{
+    let file = File::open(path)?;
+    let mut reader = BufReader::new(file);
+    private_key(&mut reader)?.ok_or_else(|| "no private key found in PEM file".into())
+}
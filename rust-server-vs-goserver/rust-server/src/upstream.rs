@@ -0,0 +1,316 @@
+//! Outbound client for fanning `/hash` out to peer benchmark servers
+//! (other language implementations), so cross-language latency can be
+//! measured from one entry point.
+//!
+//! Modeled on lightning-block-sync's REST client: each peer holds a
+//! `Mutex<Option<PeerStream>>` that connects lazily on first use and is
+//! dropped and reconnected whenever a request fails, rather than pooling
+//! a full connection pool up front.
+//!
+//! Peer connections are plain TCP unless `RUST_SERVER_UPSTREAM_TLS` is set,
+//! in which case every peer is dialed over TLS using the CA bundle at
+//! `RUST_SERVER_UPSTREAM_TLS_CA` to validate the peer's certificate —
+//! mirroring the cert/key env vars the server side uses for its own TLS
+//! (see `tls::tls_enabled`).
+
+use std::io::BufReader;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+const FIRST_BYTE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Overall budget for reading the rest of the response body once the
+/// first byte has arrived, covering the whole accumulation loop rather
+/// than resetting on every individual `read()` call.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+const TLS_ENABLED_ENV: &str = "RUST_SERVER_UPSTREAM_TLS";
+const TLS_CA_ENV: &str = "RUST_SERVER_UPSTREAM_TLS_CA";
+
+#[derive(Debug)]
+pub enum UpstreamError {
+    Connect(std::io::Error),
+    Write(std::io::Error),
+    Timeout,
+    Read(std::io::Error),
+    Malformed,
+}
+
+impl std::fmt::Display for UpstreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpstreamError::Connect(err) => write!(f, "failed to connect to peer: {err}"),
+            UpstreamError::Write(err) => write!(f, "failed to write request to peer: {err}"),
+            UpstreamError::Timeout => write!(f, "peer request timed out"),
+            UpstreamError::Read(err) => write!(f, "failed to read response from peer: {err}"),
+            UpstreamError::Malformed => write!(f, "peer response was malformed"),
+        }
+    }
+}
+
+impl std::error::Error for UpstreamError {}
+
+/// A peer connection, either plain TCP or TLS over TCP. Kept as a small
+/// enum (rather than a `Box<dyn AsyncRead + AsyncWrite>`) so the common
+/// plain-TCP path avoids a heap allocation per connection.
+enum PeerStream {
+    Plain(TcpStream),
+    Tls(Box<tokio_rustls::client::TlsStream<TcpStream>>),
+}
+
+impl AsyncRead for PeerStream {
+    fn poll_read(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            PeerStream::Plain(s) => std::pin::Pin::new(s).poll_read(cx, buf),
+            PeerStream::Tls(s) => std::pin::Pin::new(s).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for PeerStream {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            PeerStream::Plain(s) => std::pin::Pin::new(s).poll_write(cx, buf),
+            PeerStream::Tls(s) => std::pin::Pin::new(s).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            PeerStream::Plain(s) => std::pin::Pin::new(s).poll_flush(cx),
+            PeerStream::Tls(s) => std::pin::Pin::new(s).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            PeerStream::Plain(s) => std::pin::Pin::new(s).poll_shutdown(cx),
+            PeerStream::Tls(s) => std::pin::Pin::new(s).poll_shutdown(cx),
+        }
+    }
+}
+
+/// A single peer's lazily-connected, auto-reconnecting client. A dropped
+/// connection or a stalled first byte (a busy peer can sit silent before
+/// emitting anything) invalidates the cached stream so the next call
+/// reconnects instead of wedging the aggregator on one slow peer.
+pub struct PeerClient {
+    addr: String,
+    tls_connector: Option<TlsConnector>,
+    stream: Mutex<Option<PeerStream>>,
+}
+
+impl PeerClient {
+    pub fn new(addr: impl Into<String>, tls_connector: Option<TlsConnector>) -> Self {
+        Self {
+            addr: addr.into(),
+            tls_connector,
+            stream: Mutex::new(None),
+        }
+    }
+
+    /// Reads the env-configured peer list (`RUST_SERVER_UPSTREAM_PEERS`,
+    /// comma-separated `host:port` entries), dialing every peer over TLS
+    /// if `RUST_SERVER_UPSTREAM_TLS` is set.
+    pub fn peers_from_env() -> Vec<std::sync::Arc<PeerClient>> {
+        let tls_connector = tls_connector_from_env();
+        std::env::var("RUST_SERVER_UPSTREAM_PEERS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|addr| std::sync::Arc::new(PeerClient::new(addr, tls_connector.clone())))
+            .collect()
+    }
+
+    pub fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    /// Issues a plain HTTP/1.1 GET for `path`, retrying once against a
+    /// fresh connection if the cached one is dead or stalls.
+    pub async fn get(&self, path: &str) -> Result<Vec<u8>, UpstreamError> {
+        match self.get_once(path).await {
+            Ok(body) => Ok(body),
+            Err(_) => {
+                *self.stream.lock().await = None;
+                self.get_once(path).await
+            }
+        }
+    }
+
+    async fn get_once(&self, path: &str) -> Result<Vec<u8>, UpstreamError> {
+        let mut guard = self.stream.lock().await;
+
+        if guard.is_none() {
+            let tcp = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(&self.addr))
+                .await
+                .map_err(|_| UpstreamError::Timeout)?
+                .map_err(UpstreamError::Connect)?;
+
+            let stream = match &self.tls_connector {
+                Some(connector) => {
+                    let server_name = server_name_for(&self.addr).ok_or(UpstreamError::Malformed)?;
+                    let tls = connector
+                        .connect(server_name, tcp)
+                        .await
+                        .map_err(UpstreamError::Connect)?;
+                    PeerStream::Tls(Box::new(tls))
+                }
+                None => PeerStream::Plain(tcp),
+            };
+            *guard = Some(stream);
+        }
+
+        let stream = guard.as_mut().expect("connection just established");
+
+        let request = format!(
+            "GET {path} HTTP/1.1\r\nHost: {host}\r\nConnection: keep-alive\r\n\r\n",
+            path = path,
+            host = self.addr,
+        );
+
+        if let Err(err) = stream.write_all(request.as_bytes()).await {
+            *guard = None;
+            return Err(UpstreamError::Write(err));
+        }
+
+        let mut buf = vec![0u8; 8192];
+        let n = match tokio::time::timeout(FIRST_BYTE_TIMEOUT, stream.read(&mut buf)).await {
+            Ok(Ok(n)) if n > 0 => n,
+            Ok(Ok(_)) => {
+                *guard = None;
+                return Err(UpstreamError::Read(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "peer closed connection",
+                )));
+            }
+            Ok(Err(err)) => {
+                *guard = None;
+                return Err(UpstreamError::Read(err));
+            }
+            Err(_) => {
+                *guard = None;
+                return Err(UpstreamError::Timeout);
+            }
+        };
+        buf.truncate(n);
+
+        // One timeout over the whole accumulation loop, not one per
+        // `read()` call — otherwise a peer trickling a byte every few
+        // seconds could hold the connection open indefinitely.
+        let accumulated = tokio::time::timeout(READ_TIMEOUT, async {
+            loop {
+                if let Some(body) = extract_body(&buf) {
+                    return Ok(body);
+                }
+
+                let mut chunk = [0u8; 4096];
+                match stream.read(&mut chunk).await {
+                    Ok(0) => return Err(UpstreamError::Malformed),
+                    Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                    Err(err) => return Err(UpstreamError::Read(err)),
+                }
+            }
+        })
+        .await;
+
+        match accumulated {
+            Ok(Ok(body)) => Ok(body),
+            Ok(Err(err)) => {
+                *guard = None;
+                Err(err)
+            }
+            Err(_) => {
+                *guard = None;
+                Err(UpstreamError::Timeout)
+            }
+        }
+    }
+}
+
+/// Splits a raw HTTP response into headers/body and, once `Content-Length`
+/// bytes of body have arrived, returns just the body.
+fn extract_body(buf: &[u8]) -> Option<Vec<u8>> {
+    let header_end = find_subslice(buf, b"\r\n\r\n")? + 4;
+    let headers = std::str::from_utf8(&buf[..header_end]).ok()?;
+
+    let content_length: usize = headers
+        .lines()
+        .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().to_string()))
+        .and_then(|v| v.parse().ok())?;
+
+    let body = &buf[header_end..];
+    if body.len() < content_length {
+        return None;
+    }
+
+    Some(body[..content_length].to_vec())
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Builds a `TlsConnector` trusting the CA bundle at `RUST_SERVER_UPSTREAM_TLS_CA`
+/// if `RUST_SERVER_UPSTREAM_TLS` is enabled, or `None` to dial peers over plain TCP.
+fn tls_connector_from_env() -> Option<TlsConnector> {
+    if !matches!(
+        std::env::var(TLS_ENABLED_ENV).as_deref(),
+        Ok("1") | Ok("true") | Ok("TRUE")
+    ) {
+        return None;
+    }
+
+    // Ring and aws-lc-rs are both reachable transitively (e.g. via the
+    // server's own TLS support), so rustls needs this nailed down before
+    // building a ClientConfig. Ignore the error: some other call path may
+    // already have installed it.
+    let _ = tokio_rustls::rustls::crypto::aws_lc_rs::default_provider().install_default();
+
+    let ca_path = std::env::var(TLS_CA_ENV).expect("RUST_SERVER_UPSTREAM_TLS_CA must be set when RUST_SERVER_UPSTREAM_TLS is enabled");
+    let file = std::fs::File::open(&ca_path)
+        .unwrap_or_else(|err| panic!("failed to open {TLS_CA_ENV} ({ca_path}): {err}"));
+    let mut reader = BufReader::new(file);
+
+    let mut roots = RootCertStore::empty();
+    for cert in rustls_pemfile::certs(&mut reader) {
+        let cert = cert.expect("invalid certificate in upstream TLS CA bundle");
+        roots.add(cert).expect("failed to add upstream TLS CA to root store");
+    }
+
+    let config = ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    Some(TlsConnector::from(Arc::new(config)))
+}
+
+/// Extracts the `host` portion of a `host:port` peer address for use as
+/// the TLS SNI/certificate-verification name.
+fn server_name_for(addr: &str) -> Option<ServerName<'static>> {
+    let host = addr.rsplit_once(':').map(|(host, _)| host).unwrap_or(addr);
+    ServerName::try_from(host.to_string()).ok()
+}
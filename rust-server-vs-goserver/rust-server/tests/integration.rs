@@ -0,0 +1,284 @@
+//! End-to-end tests against the real server: each test spins up `run()`
+//! on an ephemeral port inside a `tokio::spawn`, then drives it with an
+//! HTTP client and asserts on the JSON it gets back. Covers the plain
+//! `/hash` + `/health` path, TLS termination (against an embedded
+//! self-signed test cert), and `/hash?aggregate=true` fan-out against an
+//! in-process mock peer, both plain-HTTP and over TLS.
+
+use serde_json::Value;
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+// Tests that set process-wide env vars (TLS cert paths, upstream peers)
+// must not run concurrently with each other. An async mutex, since the
+// guard stays held across `.await` points while the server starts up.
+static ENV_LOCK: Mutex<()> = Mutex::const_new(());
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+async fn wait_for_health(base_url: &str) {
+    let client = reqwest::Client::new();
+    for _ in 0..50 {
+        if let Ok(resp) = client.get(format!("{base_url}/health")).send().await {
+            if resp.status().is_success() {
+                return;
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("server never became healthy at {base_url}");
+}
+
+#[tokio::test]
+async fn hash_and_health_endpoints() {
+    let http_port = free_port();
+    let grpc_port = free_port();
+    let http_addr: SocketAddr = ([127, 0, 0, 1], http_port).into();
+    let grpc_addr: SocketAddr = ([127, 0, 0, 1], grpc_port).into();
+
+    tokio::spawn(rust_server::run(http_addr, grpc_addr));
+
+    let base_url = format!("http://{http_addr}");
+    wait_for_health(&base_url).await;
+
+    let client = reqwest::Client::new();
+
+    let health = client.get(format!("{base_url}/health")).send().await.unwrap();
+    assert_eq!(health.status(), reqwest::StatusCode::OK);
+
+    let hash_resp: Value = client
+        .get(format!("{base_url}/hash?algo=blake3&iters=10"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(hash_resp["source"], "rust");
+    assert_eq!(hash_resp["algo"], "blake3");
+    assert_eq!(hash_resp["iters"], 10);
+    assert_eq!(hash_resp["hash"].as_str().unwrap().len(), 64);
+}
+
+#[tokio::test]
+async fn tls_termination_serves_health() {
+    let _guard = ENV_LOCK.lock().await;
+
+    let http_port = free_port();
+    let grpc_port = free_port();
+    let http_addr: SocketAddr = ([127, 0, 0, 1], http_port).into();
+    let grpc_addr: SocketAddr = ([127, 0, 0, 1], grpc_port).into();
+
+    std::env::set_var("RUST_SERVER_TLS", "1");
+    std::env::set_var(
+        "RUST_SERVER_TLS_CERT",
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testdata/cert.pem"),
+    );
+    std::env::set_var(
+        "RUST_SERVER_TLS_KEY",
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testdata/key.pem"),
+    );
+
+    tokio::spawn(rust_server::run(http_addr, grpc_addr));
+
+    let cert = reqwest::Certificate::from_pem(include_bytes!("testdata/cert.pem")).unwrap();
+    let client = reqwest::Client::builder()
+        .add_root_certificate(cert)
+        .build()
+        .unwrap();
+
+    let base_url = format!("https://localhost:{http_port}");
+
+    let mut last_err = None;
+    let mut ok = false;
+    for _ in 0..50 {
+        match client.get(format!("{base_url}/health")).send().await {
+            Ok(resp) if resp.status().is_success() => {
+                ok = true;
+                break;
+            }
+            Ok(resp) => last_err = Some(format!("status {}", resp.status())),
+            Err(err) => last_err = Some(err.to_string()),
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    std::env::remove_var("RUST_SERVER_TLS");
+    std::env::remove_var("RUST_SERVER_TLS_CERT");
+    std::env::remove_var("RUST_SERVER_TLS_KEY");
+
+    assert!(ok, "TLS health check never succeeded: {last_err:?}");
+}
+
+#[tokio::test]
+async fn aggregate_hash_includes_mock_peer() {
+    let _guard = ENV_LOCK.lock().await;
+
+    let peer_port = free_port();
+    let peer_addr: SocketAddr = ([127, 0, 0, 1], peer_port).into();
+    tokio::spawn(serve_mock_peer(peer_addr));
+
+    let http_port = free_port();
+    let grpc_port = free_port();
+    let http_addr: SocketAddr = ([127, 0, 0, 1], http_port).into();
+    let grpc_addr: SocketAddr = ([127, 0, 0, 1], grpc_port).into();
+
+    std::env::set_var("RUST_SERVER_UPSTREAM_PEERS", peer_addr.to_string());
+    tokio::spawn(rust_server::run(http_addr, grpc_addr));
+
+    let base_url = format!("http://{http_addr}");
+    wait_for_health(&base_url).await;
+
+    let client = reqwest::Client::new();
+    let resp: Value = client
+        .get(format!("{base_url}/hash?aggregate=true"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    std::env::remove_var("RUST_SERVER_UPSTREAM_PEERS");
+
+    let peers = resp["peers"].as_array().expect("peers array");
+    assert_eq!(peers.len(), 1);
+    assert_eq!(peers[0]["addr"], peer_addr.to_string());
+    assert_eq!(peers[0]["response"]["source"], "mock-peer");
+}
+
+#[tokio::test]
+async fn aggregate_hash_includes_mock_peer_over_tls() {
+    let _guard = ENV_LOCK.lock().await;
+
+    let peer_port = free_port();
+    let peer_addr: SocketAddr = ([127, 0, 0, 1], peer_port).into();
+    tokio::spawn(serve_mock_peer_tls(peer_addr));
+
+    let http_port = free_port();
+    let grpc_port = free_port();
+    let http_addr: SocketAddr = ([127, 0, 0, 1], http_port).into();
+    let grpc_addr: SocketAddr = ([127, 0, 0, 1], grpc_port).into();
+
+    std::env::set_var("RUST_SERVER_UPSTREAM_PEERS", peer_addr.to_string());
+    std::env::set_var("RUST_SERVER_UPSTREAM_TLS", "1");
+    std::env::set_var(
+        "RUST_SERVER_UPSTREAM_TLS_CA",
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testdata/cert.pem"),
+    );
+    tokio::spawn(rust_server::run(http_addr, grpc_addr));
+
+    let base_url = format!("http://{http_addr}");
+    wait_for_health(&base_url).await;
+
+    let client = reqwest::Client::new();
+    let resp: Value = client
+        .get(format!("{base_url}/hash?aggregate=true"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    std::env::remove_var("RUST_SERVER_UPSTREAM_PEERS");
+    std::env::remove_var("RUST_SERVER_UPSTREAM_TLS");
+    std::env::remove_var("RUST_SERVER_UPSTREAM_TLS_CA");
+
+    let peers = resp["peers"].as_array().expect("peers array");
+    assert_eq!(peers.len(), 1);
+    assert_eq!(peers[0]["addr"], peer_addr.to_string());
+    assert_eq!(peers[0]["response"]["source"], "mock-peer");
+}
+
+/// A minimal plain-HTTP peer that always answers `/hash` with a fixed
+/// payload, standing in for another language's benchmark server.
+async fn serve_mock_peer(addr: SocketAddr) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    loop {
+        let (mut stream, _) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(_) => continue,
+        };
+
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf).await;
+
+            let body = r#"{"hash":"mock","algo":"sha256","iters":1,"timestamp":0,"source":"mock-peer"}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body,
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+/// Same fixed `/hash` response as `serve_mock_peer`, but terminating TLS
+/// with the same self-signed test cert the server itself uses — exercises
+/// `PeerClient`'s TLS path end to end.
+async fn serve_mock_peer_tls(addr: SocketAddr) {
+    use rustls_pemfile::{certs, private_key};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::rustls::ServerConfig;
+    use tokio_rustls::TlsAcceptor;
+
+    let _ = tokio_rustls::rustls::crypto::aws_lc_rs::default_provider().install_default();
+
+    let cert_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testdata/cert.pem");
+    let key_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/testdata/key.pem");
+
+    let chain = certs(&mut BufReader::new(std::fs::File::open(cert_path).unwrap()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let key = private_key(&mut BufReader::new(std::fs::File::open(key_path).unwrap()))
+        .unwrap()
+        .unwrap();
+
+    let config = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(chain, key)
+        .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    loop {
+        let (stream, _) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(_) => continue,
+        };
+        let acceptor = acceptor.clone();
+
+        tokio::spawn(async move {
+            let mut tls_stream = match acceptor.accept(stream).await {
+                Ok(tls_stream) => tls_stream,
+                Err(_) => return,
+            };
+
+            let mut buf = [0u8; 1024];
+            let _ = tls_stream.read(&mut buf).await;
+
+            let body = r#"{"hash":"mock","algo":"sha256","iters":1,"timestamp":0,"source":"mock-peer"}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body,
+            );
+            let _ = tls_stream.write_all(response.as_bytes()).await;
+        });
+    }
+}